@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Authentication to apply to an outgoing request, replacing the old
+/// workflow of hand-crafting an `Authorization` header line.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum Auth {
+    None,
+    Basic {
+        user: String,
+        pass: String,
+    },
+    Bearer {
+        token: String,
+    },
+    OAuth2ClientCredentials {
+        token_url: String,
+        client_id: String,
+        client_secret: String,
+        scopes: Option<String>,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+struct CachedToken {
+    access_token: String,
+    expires_at_ms: u128,
+}
+
+/// Managed state caching OAuth2 client-credentials tokens per
+/// `token_url`+`client_id`, so a token is fetched once and reused until it
+/// expires instead of on every request.
+#[derive(Default)]
+pub struct OAuth2TokenStore {
+    tokens: Mutex<HashMap<String, CachedToken>>,
+}
+
+fn now_ms() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+impl OAuth2TokenStore {
+    pub fn new() -> Self {
+        OAuth2TokenStore::default()
+    }
+
+    async fn get_token(
+        &self,
+        client: &reqwest::Client,
+        token_url: &str,
+        client_id: &str,
+        client_secret: &str,
+        scopes: Option<&str>,
+    ) -> Result<String, String> {
+        let key = format!("{token_url}::{client_id}");
+
+        if let Some(cached) = self.tokens.lock().map_err(|e| e.to_string())?.get(&key) {
+            if cached.expires_at_ms > now_ms() {
+                return Ok(cached.access_token.clone());
+            }
+        }
+
+        let mut form = vec![
+            ("grant_type", "client_credentials"),
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+        ];
+        if let Some(scopes) = scopes {
+            form.push(("scope", scopes));
+        }
+
+        let response = client
+            .post(token_url)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "token request to '{}' failed with status {}",
+                token_url,
+                response.status()
+            ));
+        }
+        let token: TokenResponse = response.json().await.map_err(|e| e.to_string())?;
+
+        let expires_in_ms = token.expires_in.unwrap_or(300).saturating_mul(1000);
+        let expires_at_ms = now_ms() + expires_in_ms as u128;
+
+        self.tokens.lock().map_err(|e| e.to_string())?.insert(
+            key,
+            CachedToken {
+                access_token: token.access_token.clone(),
+                expires_at_ms,
+            },
+        );
+
+        Ok(token.access_token)
+    }
+}
+
+/// Applies the requested `Auth` to `request_builder`, fetching and caching
+/// an OAuth2 token via `token_store` when needed.
+pub async fn apply_auth(
+    request_builder: reqwest::RequestBuilder,
+    auth: Auth,
+    token_store: &OAuth2TokenStore,
+) -> Result<reqwest::RequestBuilder, String> {
+    match auth {
+        Auth::None => Ok(request_builder),
+        Auth::Basic { user, pass } => Ok(request_builder.basic_auth(user, Some(pass))),
+        Auth::Bearer { token } => Ok(request_builder.bearer_auth(token)),
+        Auth::OAuth2ClientCredentials {
+            token_url,
+            client_id,
+            client_secret,
+            scopes,
+        } => {
+            let client = reqwest::Client::new();
+            let token = token_store
+                .get_token(&client, &token_url, &client_id, &client_secret, scopes.as_deref())
+                .await?;
+            Ok(request_builder.bearer_auth(token))
+        }
+    }
+}
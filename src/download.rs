@@ -0,0 +1,100 @@
+use futures_util::StreamExt;
+use serde::Serialize;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+/// Progress emitted to the frontend while a download is in flight.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    pub bytes_received: u64,
+    pub total_bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadResult {
+    pub path: String,
+    pub total_bytes: u64,
+}
+
+/// Streams `url` to `destination` chunk by chunk instead of buffering the
+/// whole body, so large downloads don't blow up memory or block the
+/// command until the transfer finishes.
+///
+/// When `resume` is true and `destination` already exists, the download
+/// continues from the existing file's length via a `Range: bytes=<offset>-`
+/// request and appends rather than overwriting. If the server ignores the
+/// range and answers 200 instead of 206, the file is rewritten from the start.
+#[tauri::command]
+pub async fn download_file(
+    url: String,
+    destination: String,
+    resume: bool,
+    progress: tauri::ipc::Channel<DownloadProgress>,
+) -> Result<DownloadResult, String> {
+    let requested_offset = if resume {
+        tokio::fs::metadata(&destination)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    let client = reqwest::Client::new();
+    let mut request = client.get(&url);
+    if requested_offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", requested_offset));
+    }
+
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("unexpected status: {}", response.status()));
+    }
+
+    // The server only actually resumed if it answered with 206 Partial
+    // Content; on a plain 200 it sent the full body from the start, even
+    // though a Range header was requested, so fall back to a fresh download.
+    let resumed = requested_offset > 0 && response.status().as_u16() == 206;
+    let start_offset = if resumed { requested_offset } else { 0 };
+
+    let total_bytes = if resumed {
+        response
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.rsplit('/').next())
+            .and_then(|v| v.parse::<u64>().ok())
+            .or_else(|| response.content_length().map(|len| len + start_offset))
+    } else {
+        response.content_length()
+    };
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(!resumed)
+        .open(&destination)
+        .await
+        .map_err(|e| e.to_string())?;
+    file.seek(std::io::SeekFrom::Start(start_offset))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut bytes_received = start_offset;
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        file.write_all(&chunk).await.map_err(|e| e.to_string())?;
+        bytes_received += chunk.len() as u64;
+        let _ = progress.send(DownloadProgress {
+            bytes_received,
+            total_bytes,
+        });
+    }
+    file.flush().await.map_err(|e| e.to_string())?;
+
+    Ok(DownloadResult {
+        path: destination,
+        total_bytes: bytes_received,
+    })
+}
@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// A named set of variables that can be referenced as `{{var}}` in a
+/// request's url, headers, and body.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Environment {
+    pub name: String,
+    pub variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct EnvironmentData {
+    environments: Vec<Environment>,
+    active_environment: Option<String>,
+}
+
+/// Managed state holding environments and the active selection, persisted
+/// as a single JSON file in the app data dir.
+pub struct EnvironmentStore {
+    path: PathBuf,
+    data: Mutex<EnvironmentData>,
+}
+
+impl EnvironmentStore {
+    pub fn load(app_data_dir: &Path) -> Self {
+        let path = app_data_dir.join("environments.json");
+        let data = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        EnvironmentStore {
+            path,
+            data: Mutex::new(data),
+        }
+    }
+
+    fn persist(&self, data: &EnvironmentData) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let contents = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, contents).map_err(|e| e.to_string())
+    }
+
+    pub fn create_environment(&self, name: String) -> Result<(), String> {
+        let mut data = self.data.lock().map_err(|e| e.to_string())?;
+        if data.environments.iter().any(|e| e.name == name) {
+            return Err(format!("environment '{}' already exists", name));
+        }
+        data.environments.push(Environment {
+            name,
+            variables: HashMap::new(),
+        });
+        self.persist(&data)
+    }
+
+    pub fn set_active_environment(&self, name: String) -> Result<(), String> {
+        let mut data = self.data.lock().map_err(|e| e.to_string())?;
+        if !data.environments.iter().any(|e| e.name == name) {
+            return Err(format!("environment '{}' not found", name));
+        }
+        data.active_environment = Some(name);
+        self.persist(&data)
+    }
+
+    pub fn set_variable(&self, environment: &str, key: String, value: String) -> Result<(), String> {
+        let mut data = self.data.lock().map_err(|e| e.to_string())?;
+        let env = data
+            .environments
+            .iter_mut()
+            .find(|e| e.name == environment)
+            .ok_or_else(|| format!("environment '{}' not found", environment))?;
+        env.variables.insert(key, value);
+        self.persist(&data)
+    }
+
+    pub fn list_environments(&self) -> Result<Vec<Environment>, String> {
+        let data = self.data.lock().map_err(|e| e.to_string())?;
+        Ok(data.environments.clone())
+    }
+
+    /// Variables of the currently active environment, or `None` if no
+    /// environment is active. `None` means `{{...}}` in the request should be
+    /// left untouched rather than treated as unresolved placeholders.
+    pub fn active_variables(&self) -> Result<Option<HashMap<String, String>>, String> {
+        let data = self.data.lock().map_err(|e| e.to_string())?;
+        let active = match &data.active_environment {
+            Some(name) => name,
+            None => return Ok(None),
+        };
+        Ok(Some(
+            data.environments
+                .iter()
+                .find(|e| &e.name == active)
+                .map(|e| e.variables.clone())
+                .unwrap_or_default(),
+        ))
+    }
+}
+
+/// Replaces every `{{var}}` placeholder in `input` with its value from
+/// `variables`. Returns an error naming every placeholder that has no
+/// matching variable instead of leaving it unresolved in the request.
+pub fn resolve_placeholders(input: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    let mut result = String::new();
+    let mut missing = Vec::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find("{{") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].trim();
+                match variables.get(name) {
+                    Some(value) => result.push_str(value),
+                    None => missing.push(name.to_string()),
+                }
+                rest = &after[end + 2..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+                break;
+            }
+        }
+    }
+    result.push_str(rest);
+
+    if missing.is_empty() {
+        Ok(result)
+    } else {
+        Err(format!("unknown variable(s): {}", missing.join(", ")))
+    }
+}
+
+#[tauri::command]
+pub fn create_environment(
+    store: tauri::State<'_, EnvironmentStore>,
+    name: String,
+) -> Result<(), String> {
+    store.create_environment(name)
+}
+
+#[tauri::command]
+pub fn set_active_environment(
+    store: tauri::State<'_, EnvironmentStore>,
+    name: String,
+) -> Result<(), String> {
+    store.set_active_environment(name)
+}
+
+#[tauri::command]
+pub fn set_variable(
+    store: tauri::State<'_, EnvironmentStore>,
+    environment: String,
+    key: String,
+    value: String,
+) -> Result<(), String> {
+    store.set_variable(&environment, key, value)
+}
+
+#[tauri::command]
+pub fn list_environments(
+    store: tauri::State<'_, EnvironmentStore>,
+) -> Result<Vec<Environment>, String> {
+    store.list_environments()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_placeholders_hit() {
+        let variables = vars(&[("host", "example.com"), ("id", "42")]);
+        let result = resolve_placeholders("https://{{host}}/users/{{id}}", &variables);
+        assert_eq!(result, Ok("https://example.com/users/42".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_placeholders_no_placeholders() {
+        let variables = vars(&[]);
+        let result = resolve_placeholders("https://example.com/users/42", &variables);
+        assert_eq!(result, Ok("https://example.com/users/42".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_placeholders_miss() {
+        let variables = vars(&[("host", "example.com")]);
+        let result = resolve_placeholders("https://{{host}}/{{missing}}", &variables);
+        assert_eq!(result, Err("unknown variable(s): missing".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_placeholders_multiple_misses_are_all_reported() {
+        let variables = vars(&[]);
+        let result = resolve_placeholders("{{a}}-{{b}}", &variables);
+        assert_eq!(result, Err("unknown variable(s): a, b".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_placeholders_unterminated_is_left_literal() {
+        let variables = vars(&[("host", "example.com")]);
+        let result = resolve_placeholders("https://{{host}}/path?q={{", &variables);
+        assert_eq!(result, Ok("https://example.com/path?q={{".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_placeholders_trims_whitespace_in_name() {
+        let variables = vars(&[("host", "example.com")]);
+        let result = resolve_placeholders("{{ host }}", &variables);
+        assert_eq!(result, Ok("example.com".to_string()));
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// A single executed request, recorded after `execute_request` completes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: u64,
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    pub duration_ms: u128,
+    pub timestamp_ms: u128,
+}
+
+/// A named group of saved requests, each stored as a raw JSON blob so any
+/// request shape the frontend produces can be re-run later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    pub requests: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryData {
+    next_id: u64,
+    entries: Vec<HistoryEntry>,
+    collections: Vec<Collection>,
+}
+
+/// Managed state holding request history and collections, persisted as a
+/// single JSON file in the app data dir. Registered with `.manage(...)` and
+/// retrieved in commands via `State<'_, HistoryStore>`.
+pub struct HistoryStore {
+    path: PathBuf,
+    data: Mutex<HistoryData>,
+}
+
+impl HistoryStore {
+    pub fn load(app_data_dir: &Path) -> Self {
+        let path = app_data_dir.join("history.json");
+        let data = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        HistoryStore {
+            path,
+            data: Mutex::new(data),
+        }
+    }
+
+    fn persist(&self, data: &HistoryData) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let contents = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+        std::fs::write(&self.path, contents).map_err(|e| e.to_string())
+    }
+
+    pub fn push_entry(
+        &self,
+        method: String,
+        url: String,
+        status: u16,
+        duration_ms: u128,
+        timestamp_ms: u128,
+    ) -> Result<HistoryEntry, String> {
+        let mut data = self.data.lock().map_err(|e| e.to_string())?;
+        let id = data.next_id;
+        data.next_id += 1;
+
+        let entry = HistoryEntry {
+            id,
+            method,
+            url,
+            status,
+            duration_ms,
+            timestamp_ms,
+        };
+        data.entries.push(entry.clone());
+        self.persist(&data)?;
+        Ok(entry)
+    }
+
+    pub fn list_entries(&self) -> Result<Vec<HistoryEntry>, String> {
+        let data = self.data.lock().map_err(|e| e.to_string())?;
+        Ok(data.entries.clone())
+    }
+
+    pub fn get_entry(&self, id: u64) -> Result<Option<HistoryEntry>, String> {
+        let data = self.data.lock().map_err(|e| e.to_string())?;
+        Ok(data.entries.iter().find(|e| e.id == id).cloned())
+    }
+
+    pub fn clear(&self) -> Result<(), String> {
+        let mut data = self.data.lock().map_err(|e| e.to_string())?;
+        data.entries.clear();
+        self.persist(&data)
+    }
+
+    pub fn create_collection(&self, name: String) -> Result<(), String> {
+        let mut data = self.data.lock().map_err(|e| e.to_string())?;
+        if data.collections.iter().any(|c| c.name == name) {
+            return Err(format!("collection '{}' already exists", name));
+        }
+        data.collections.push(Collection {
+            name,
+            requests: Vec::new(),
+        });
+        self.persist(&data)
+    }
+
+    pub fn add_to_collection(&self, name: &str, request: serde_json::Value) -> Result<(), String> {
+        let mut data = self.data.lock().map_err(|e| e.to_string())?;
+        let collection = data
+            .collections
+            .iter_mut()
+            .find(|c| c.name == name)
+            .ok_or_else(|| format!("collection '{}' not found", name))?;
+        collection.requests.push(request);
+        self.persist(&data)
+    }
+
+    pub fn list_collections(&self) -> Result<Vec<Collection>, String> {
+        let data = self.data.lock().map_err(|e| e.to_string())?;
+        Ok(data.collections.clone())
+    }
+}
+
+#[tauri::command]
+pub fn list_history(store: tauri::State<'_, HistoryStore>) -> Result<Vec<HistoryEntry>, String> {
+    store.list_entries()
+}
+
+#[tauri::command]
+pub fn get_history_entry(
+    store: tauri::State<'_, HistoryStore>,
+    id: u64,
+) -> Result<Option<HistoryEntry>, String> {
+    store.get_entry(id)
+}
+
+#[tauri::command]
+pub fn clear_history(store: tauri::State<'_, HistoryStore>) -> Result<(), String> {
+    store.clear()
+}
+
+#[tauri::command]
+pub fn create_collection(
+    store: tauri::State<'_, HistoryStore>,
+    name: String,
+) -> Result<(), String> {
+    store.create_collection(name)
+}
+
+#[tauri::command]
+pub fn add_to_collection(
+    store: tauri::State<'_, HistoryStore>,
+    name: String,
+    request: serde_json::Value,
+) -> Result<(), String> {
+    store.add_to_collection(&name, request)
+}
+
+#[tauri::command]
+pub fn list_collections(store: tauri::State<'_, HistoryStore>) -> Result<Vec<Collection>, String> {
+    store.list_collections()
+}
@@ -1,28 +1,392 @@
+mod auth;
+mod download;
+mod environments;
+mod history;
+
+use auth::{Auth, OAuth2TokenStore};
+use base64::Engine;
+use environments::EnvironmentStore;
+use history::HistoryStore;
 use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use tauri::Manager;
+
+/// How the frontend wants the response body decoded.
+///
+/// `Auto` inspects the `Content-Type` header and picks the best
+/// representation; the other variants force a specific one regardless of
+/// what the server reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum ResponseType {
+    Auto,
+    Json,
+    Text,
+    Binary,
+}
+
+impl Default for ResponseType {
+    fn default() -> Self {
+        ResponseType::Auto
+    }
+}
+
+/// How the outgoing request body should be constructed.
+///
+/// `Raw` preserves the original plain-text body behavior; the other variants
+/// cover the request shapes a text box alone can't express.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+enum RequestBody {
+    Raw { content: String },
+    UrlEncodedForm { fields: Vec<(String, String)> },
+    MultipartForm { fields: Vec<MultipartField> },
+    File { path: String },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum MultipartField {
+    Text { name: String, value: String },
+    File { name: String, path: String },
+}
+
+/// Guesses a `Content-Type` from a file extension for uploads where the
+/// caller hasn't set one explicitly. Falls back to a generic binary type.
+fn guess_content_type(path: &std::path::Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "json" => "application/json",
+        "txt" => "text/plain",
+        "html" | "htm" => "text/html",
+        "xml" => "application/xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Resolves `{{var}}` placeholders in the value fields of a `RequestBody`
+/// (form field values, multipart field values and file paths, the file
+/// upload path, and the raw body text). Form field keys and multipart field
+/// names are left as they are.
+fn resolve_request_body_placeholders(
+    body: RequestBody,
+    variables: &std::collections::HashMap<String, String>,
+) -> Result<RequestBody, String> {
+    Ok(match body {
+        RequestBody::Raw { content } => RequestBody::Raw {
+            content: environments::resolve_placeholders(&content, variables)?,
+        },
+        RequestBody::UrlEncodedForm { fields } => RequestBody::UrlEncodedForm {
+            fields: fields
+                .into_iter()
+                .map(|(key, value)| -> Result<_, String> {
+                    Ok((key, environments::resolve_placeholders(&value, variables)?))
+                })
+                .collect::<Result<_, _>>()?,
+        },
+        RequestBody::MultipartForm { fields } => RequestBody::MultipartForm {
+            fields: fields
+                .into_iter()
+                .map(|field| -> Result<_, String> {
+                    Ok(match field {
+                        MultipartField::Text { name, value } => MultipartField::Text {
+                            name,
+                            value: environments::resolve_placeholders(&value, variables)?,
+                        },
+                        MultipartField::File { name, path } => MultipartField::File {
+                            name,
+                            path: environments::resolve_placeholders(&path, variables)?,
+                        },
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        },
+        RequestBody::File { path } => RequestBody::File {
+            path: environments::resolve_placeholders(&path, variables)?,
+        },
+    })
+}
+
+async fn apply_request_body(
+    mut request_builder: reqwest::RequestBuilder,
+    body: RequestBody,
+) -> Result<reqwest::RequestBuilder, String> {
+    match body {
+        RequestBody::Raw { content } => {
+            if !content.trim().is_empty() {
+                request_builder = request_builder.body(content);
+            }
+        }
+        RequestBody::UrlEncodedForm { fields } => {
+            request_builder = request_builder.form(&fields);
+        }
+        RequestBody::MultipartForm { fields } => {
+            let mut form = reqwest::multipart::Form::new();
+            for field in fields {
+                form = match field {
+                    MultipartField::Text { name, value } => form.text(name, value),
+                    MultipartField::File { name, path } => {
+                        let part = reqwest::multipart::Part::file(&path)
+                            .await
+                            .map_err(|e| format!("failed to read file '{}': {}", path, e))?;
+                        form.part(name, part)
+                    }
+                };
+            }
+            request_builder = request_builder.multipart(form);
+        }
+        RequestBody::File { path } => {
+            let path = std::path::PathBuf::from(path);
+            let content_type = guess_content_type(&path);
+            let file = tokio::fs::File::open(&path)
+                .await
+                .map_err(|e| format!("failed to open file '{}': {}", path.display(), e))?;
+            let metadata = file
+                .metadata()
+                .await
+                .map_err(|e| format!("failed to stat file '{}': {}", path.display(), e))?;
+            let stream = tokio_util::codec::FramedRead::new(file, tokio_util::codec::BytesCodec::new());
+            let has_content_type = request_builder
+                .try_clone()
+                .and_then(|b| b.build().ok())
+                .map_or(false, |r| r.headers().contains_key(reqwest::header::CONTENT_TYPE));
+            if !has_content_type {
+                request_builder = request_builder.header(reqwest::header::CONTENT_TYPE, content_type);
+            }
+            request_builder = request_builder
+                .header(reqwest::header::CONTENT_LENGTH, metadata.len())
+                .body(reqwest::Body::wrap_stream(stream));
+        }
+    }
+    Ok(request_builder)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+enum ResponseBody {
+    Json { value: serde_json::Value },
+    Text { value: String },
+    Binary { base64: String },
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct HttpResponse {
     status: u16,
     status_text: String,
     headers: String,
-    body: serde_json::Value,
+    body: ResponseBody,
+    content_type: Option<String>,
+    byte_len: usize,
+    elapsed_ms: u128,
+}
+
+/// Per-request overrides for timeouts, redirects, and transport behavior.
+///
+/// Defaults mirror what a sane HTTP client would do out of the box, but every
+/// field is explicit so the frontend can surface them as editable options.
+#[derive(Debug, Serialize, Deserialize)]
+struct RequestOptions {
+    #[serde(default = "default_true")]
+    follow_redirects: bool,
+    #[serde(default = "default_max_redirections")]
+    max_redirections: u32,
+    connect_timeout_ms: Option<u64>,
+    read_timeout_ms: Option<u64>,
+    total_timeout_ms: Option<u64>,
+    #[serde(default = "default_true")]
+    allow_compression: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_redirections() -> u32 {
+    10
+}
+
+impl Default for RequestOptions {
+    fn default() -> Self {
+        RequestOptions {
+            follow_redirects: true,
+            max_redirections: default_max_redirections(),
+            connect_timeout_ms: None,
+            read_timeout_ms: None,
+            total_timeout_ms: None,
+            allow_compression: true,
+        }
+    }
+}
+
+struct DecodedBody {
+    body: ResponseBody,
+    byte_len: usize,
+}
+
+/// Picks the concrete `ResponseType` to decode with. `Auto` inspects the
+/// `Content-Type` header: JSON for `application/json` (or any `+json`
+/// suffix), text for `text/*`, and binary for everything else. Any other
+/// requested type passes through unchanged.
+fn resolve_response_type(response_type: ResponseType, content_type: Option<&str>) -> ResponseType {
+    match response_type {
+        ResponseType::Auto => {
+            let mime = content_type.unwrap_or("").to_ascii_lowercase();
+            let mime = mime.split(';').next().unwrap_or("").trim();
+            if mime.contains("application/json") || mime.ends_with("+json") {
+                ResponseType::Json
+            } else if mime.starts_with("text/") {
+                ResponseType::Text
+            } else {
+                ResponseType::Binary
+            }
+        }
+        other => other,
+    }
+}
+
+/// Decodes a response according to the requested `ResponseType`.
+///
+/// In `Auto` mode the `Content-Type` header decides: JSON for
+/// `application/json`, text for `text/*`, and base64-encoded bytes for
+/// everything else (images, PDFs, protobufs, and other non-UTF8 payloads).
+async fn decode_response_body(
+    response: reqwest::Response,
+    response_type: ResponseType,
+    content_type: Option<&str>,
+) -> Result<DecodedBody, reqwest::Error> {
+    let resolved_type = resolve_response_type(response_type, content_type);
+
+    match resolved_type {
+        ResponseType::Json => {
+            let text = response.text().await?;
+            let byte_len = text.len();
+            let value = serde_json::from_str::<serde_json::Value>(&text)
+                .unwrap_or(serde_json::Value::String(text));
+            Ok(DecodedBody {
+                body: ResponseBody::Json { value },
+                byte_len,
+            })
+        }
+        ResponseType::Text => {
+            let text = response.text().await?;
+            let byte_len = text.len();
+            Ok(DecodedBody {
+                body: ResponseBody::Text { value: text },
+                byte_len,
+            })
+        }
+        ResponseType::Binary | ResponseType::Auto => {
+            let bytes = response.bytes().await?;
+            let byte_len = bytes.len();
+            let base64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
+            Ok(DecodedBody {
+                body: ResponseBody::Binary { base64 },
+                byte_len,
+            })
+        }
+    }
 }
 
 #[tauri::command]
 async fn execute_request(
+    history: tauri::State<'_, HistoryStore>,
+    token_store: tauri::State<'_, OAuth2TokenStore>,
+    environments: tauri::State<'_, EnvironmentStore>,
     method: String,
     url: String,
     headers: String,
-    body: Option<String>,
+    body: Option<RequestBody>,
+    options: Option<RequestOptions>,
+    response_type: Option<ResponseType>,
+    auth: Option<Auth>,
 ) -> Result<HttpResponse, String> {
-    let client = reqwest::Client::new();
-    
+    execute_request_inner(
+        &history,
+        &token_store,
+        &environments,
+        method,
+        url,
+        headers,
+        body,
+        options,
+        response_type,
+        auth,
+    )
+    .await
+}
+
+/// Core request execution, kept separate from the `#[tauri::command]`
+/// wrapper so it can be exercised directly (e.g. in tests) without a running
+/// Tauri app to extract `State` from.
+#[allow(clippy::too_many_arguments)]
+async fn execute_request_inner(
+    history: &HistoryStore,
+    token_store: &OAuth2TokenStore,
+    environments: &EnvironmentStore,
+    method: String,
+    url: String,
+    headers: String,
+    body: Option<RequestBody>,
+    options: Option<RequestOptions>,
+    response_type: Option<ResponseType>,
+    auth: Option<Auth>,
+) -> Result<HttpResponse, String> {
+    let response_type = response_type.unwrap_or_default();
+    let options = options.unwrap_or_default();
+
+    // Only treat `{{...}}` as a placeholder when an environment is active;
+    // otherwise leave it untouched so literal `{{`/`}}` (e.g. a templating
+    // payload) in the request doesn't get rejected as an unknown variable.
+    let (url, headers, body) = match environments.active_variables()? {
+        Some(variables) => {
+            let url = environments::resolve_placeholders(&url, &variables)?;
+            let headers = environments::resolve_placeholders(&headers, &variables)?;
+            let body = body
+                .map(|body| resolve_request_body_placeholders(body, &variables))
+                .transpose()?;
+            (url, headers, body)
+        }
+        None => (url, headers, body),
+    };
+
+    let redirect_policy = if options.follow_redirects {
+        reqwest::redirect::Policy::limited(options.max_redirections as usize)
+    } else {
+        reqwest::redirect::Policy::none()
+    };
+
+    let mut client_builder = reqwest::ClientBuilder::new()
+        .redirect(redirect_policy)
+        .gzip(options.allow_compression)
+        .brotli(options.allow_compression);
+
+    if let Some(connect_timeout_ms) = options.connect_timeout_ms {
+        client_builder =
+            client_builder.connect_timeout(std::time::Duration::from_millis(connect_timeout_ms));
+    }
+    if let Some(total_timeout_ms) = options.total_timeout_ms {
+        client_builder = client_builder.timeout(std::time::Duration::from_millis(total_timeout_ms));
+    }
+    if let Some(read_timeout_ms) = options.read_timeout_ms {
+        client_builder =
+            client_builder.read_timeout(std::time::Duration::from_millis(read_timeout_ms));
+    }
+
+    let client = client_builder.build().map_err(|e| e.to_string())?;
+
     let mut request_builder = match method.as_str() {
         "GET" => client.get(&url),
         "POST" => client.post(&url),
         "PUT" => client.put(&url),
         "PATCH" => client.patch(&url),
         "DELETE" => client.delete(&url),
+        "OPTIONS" => client.request(reqwest::Method::OPTIONS, &url),
+        "HEAD" => client.head(&url),
+        "TRACE" => client.request(reqwest::Method::TRACE, &url),
         _ => return Err("Unsupported HTTP method".to_string()),
     };
 
@@ -41,36 +405,52 @@ async fn execute_request(
     }
 
     // Add body if present
-    if let Some(body_content) = body {
-        if !body_content.trim().is_empty() {
-            request_builder = request_builder.body(body_content);
-        }
+    if let Some(body) = body {
+        request_builder = apply_request_body(request_builder, body).await?;
     }
 
+    if let Some(auth) = auth {
+        request_builder = auth::apply_auth(request_builder, auth, token_store).await?;
+    }
+
+    let started_at = Instant::now();
+
     match request_builder.send().await {
         Ok(response) => {
             let status = response.status().as_u16();
             let status_text = response.status().canonical_reason().unwrap_or("Unknown").to_string();
-            
+
             // Format response headers
             let mut headers_text = String::new();
             for (name, value) in response.headers() {
                 headers_text.push_str(&format!("{}: {}\n", name.as_str(), value.to_str().unwrap_or("")));
             }
-            
-            let text = response.text().await.map_err(|e| e.to_string())?;
-            
-            // Try to parse as JSON, fallback to string
-            let body = match serde_json::from_str::<serde_json::Value>(&text) {
-                Ok(json) => json,
-                Err(_) => serde_json::Value::String(text),
-            };
-            
-            Ok(HttpResponse { 
+
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_string());
+
+            let body = decode_response_body(response, response_type, content_type.as_deref())
+                .await
+                .map_err(|e| e.to_string())?;
+            let elapsed_ms = started_at.elapsed().as_millis();
+
+            let timestamp_ms = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0);
+            history.push_entry(method, url, status, elapsed_ms, timestamp_ms)?;
+
+            Ok(HttpResponse {
                 status,
                 status_text,
                 headers: headers_text,
-                body 
+                byte_len: body.byte_len,
+                body: body.body,
+                content_type,
+                elapsed_ms,
             })
         }
         Err(e) => Err(e.to_string()),
@@ -102,14 +482,14 @@ async fn save_request(app_handle: tauri::AppHandle, request: String) -> Result<(
 async fn load_request(app_handle: tauri::AppHandle) -> Result<String, String> {
     use tauri_plugin_dialog::DialogExt;
     use std::fs;
-    
+
     let file_path = app_handle
         .dialog()
         .file()
         .add_filter("JSON files", &["json"])
         .add_filter("All files", &["*"])
         .blocking_pick_file();
-    
+
     if let Some(path) = file_path {
         fs::read_to_string(path.as_path().unwrap()).map_err(|e| e.to_string())
     } else {
@@ -117,6 +497,43 @@ async fn load_request(app_handle: tauri::AppHandle) -> Result<String, String> {
     }
 }
 
+/// Saves a response body (as returned in `HttpResponse::body`) to a
+/// user-chosen file, decoding the base64 payload for binary responses.
+#[tauri::command]
+async fn save_response_body(
+    app_handle: tauri::AppHandle,
+    body: ResponseBody,
+) -> Result<(), String> {
+    use std::fs;
+    use tauri_plugin_dialog::DialogExt;
+
+    let file_path = app_handle
+        .dialog()
+        .file()
+        .add_filter("All files", &["*"])
+        .set_file_name("response.bin")
+        .blocking_save_file();
+
+    let Some(path) = file_path else {
+        return Err("Save cancelled".to_string());
+    };
+    let path = path.as_path().unwrap();
+
+    match body {
+        ResponseBody::Json { value } => {
+            let text = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+            fs::write(path, text).map_err(|e| e.to_string())
+        }
+        ResponseBody::Text { value } => fs::write(path, value).map_err(|e| e.to_string()),
+        ResponseBody::Binary { base64 } => {
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(base64)
+                .map_err(|e| e.to_string())?;
+            fs::write(path, bytes).map_err(|e| e.to_string())
+        }
+    }
+}
+
 fn main() {
     // Set WebKit environment variable for Linux compatibility
     #[cfg(target_os = "linux")]
@@ -126,10 +543,29 @@ fn main() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
+        .setup(|app| {
+            let app_data_dir = app.path().app_data_dir()?;
+            app.manage(HistoryStore::load(&app_data_dir));
+            app.manage(OAuth2TokenStore::new());
+            app.manage(EnvironmentStore::load(&app_data_dir));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             execute_request,
             save_request,
-            load_request
+            load_request,
+            save_response_body,
+            history::list_history,
+            history::get_history_entry,
+            history::clear_history,
+            history::create_collection,
+            history::add_to_collection,
+            history::list_collections,
+            download::download_file,
+            environments::create_environment,
+            environments::set_active_environment,
+            environments::set_variable,
+            environments::list_environments
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -146,7 +582,23 @@ mod tests {
         let headers = "".to_string();
         let body = None;
 
-        let result = execute_request(method, url, headers, body).await;
+        let history = HistoryStore::load(&std::env::temp_dir().join("purehttp-test-history"));
+        let token_store = OAuth2TokenStore::new();
+        let environments =
+            EnvironmentStore::load(&std::env::temp_dir().join("purehttp-test-environments"));
+        let result = execute_request_inner(
+            &history,
+            &token_store,
+            &environments,
+            method,
+            url,
+            headers,
+            body,
+            None,
+            None,
+            None,
+        )
+        .await;
         
         match result {
             Ok(response) => {
@@ -160,6 +612,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_response_type_auto_by_content_type() {
+        let cases = [
+            (Some("application/json"), ResponseType::Json),
+            (Some("application/json; charset=utf-8"), ResponseType::Json),
+            (Some("application/vnd.api+json"), ResponseType::Json),
+            (Some("text/plain"), ResponseType::Text),
+            (Some("text/html; charset=utf-8"), ResponseType::Text),
+            (Some("image/png"), ResponseType::Binary),
+            (Some("application/octet-stream"), ResponseType::Binary),
+            (None, ResponseType::Binary),
+        ];
+
+        for (content_type, expected) in cases {
+            assert_eq!(
+                resolve_response_type(ResponseType::Auto, content_type),
+                expected,
+                "content_type {:?}",
+                content_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolve_response_type_explicit_overrides_content_type() {
+        assert_eq!(
+            resolve_response_type(ResponseType::Text, Some("application/json")),
+            ResponseType::Text
+        );
+        assert_eq!(
+            resolve_response_type(ResponseType::Binary, Some("text/plain")),
+            ResponseType::Binary
+        );
+    }
+
     #[tokio::test]
     async fn test_save_and_load_request() {
         let test_request = r#"{